@@ -1,13 +1,40 @@
 use crate::into_view::{IntoView, View};
+use either_of::Either;
+use leptos_server::Resource;
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    computed::Memo,
+    owner::{provide_context, Owner},
+    signal::ArcRwSignal,
+    traits::Get,
+};
 use std::{
+    borrow::Cow,
+    collections::HashMap,
     fmt::{self, Debug},
-    sync::Arc,
+    future::Future,
+    hash::Hash,
+    panic::Location,
+    pin::Pin,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, RwLock, Weak,
+    },
+    thread::{self, ThreadId},
+    time::Duration,
 };
-use tachys::view::{
-    any_view::{AnyView, IntoAny},
-    fragment::{Fragment, IntoFragment},
-    RenderHtml,
+use tachys::{
+    html::attribute::Attribute,
+    reactive_graph::OwnedView,
+    view::{
+        add_attr::AddAnyAttr,
+        any_view::{AnyView, IntoAny},
+        fragment::{Fragment, IntoFragment},
+        RenderHtml,
+    },
 };
+use throw_error::Error as AnyError;
 
 /// The most common type for the `children` property on components,
 /// which can only be called once.
@@ -18,6 +45,11 @@ pub type Children = Box<dyn FnOnce() -> AnyView + Send>;
 
 /// A type for the `children` property on components that can be called only once,
 /// and provides a collection of all the children passed to this component.
+///
+/// Calling it returns a [`Fragment`] with one view per top-level child, which can be inspected
+/// with [`Fragment::len`] and [`Fragment::iter`], or iterated over by value to take the individual
+/// views. This builds every child, so to know how many children were passed without rendering
+/// them, take [`TypedChildren`] instead and use [`TypedChildren::count`].
 pub type ChildrenFragment = Box<dyn FnOnce() -> Fragment + Send>;
 
 /// A type for the `children` property on components that can be called
@@ -37,8 +69,103 @@ pub type ChildrenFnMut = Box<dyn FnMut() -> AnyView + Send>;
 /// passed to this component.
 pub type ChildrenFragmentMut = Box<dyn FnMut() -> Fragment + Send>;
 
-// This is to still support components that accept `Box<dyn Fn() -> AnyView>` as a children.
-type BoxedChildrenFn = Box<dyn Fn() -> AnyView + Send>;
+/// A type for the `children` property on components that can be called only once, and whose
+/// children may fail to be constructed.
+///
+/// The error is the same type collected by `<ErrorBoundary/>`, so the `Result` can be rendered
+/// directly inside an error boundary to surface the error.
+pub type FallibleChildren =
+    Box<dyn FnOnce() -> Result<AnyView, AnyError> + Send>;
+
+/// A type for the `children` property on components that can be called only once, and whose
+/// children are built asynchronously.
+///
+/// Calling it returns a future that resolves to the children. The component is expected to
+/// await it in a [`Suspend`](tachys::reactive_graph::Suspend), so that an enclosing
+/// `<Suspense/>` shows its fallback until the children are ready.
+///
+/// The future must be `Send`. See [`ChildrenAsyncLocal`] for futures that are not, such as most
+/// browser futures.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// #[component]
+/// fn Deferred(children: ChildrenAsync) -> impl IntoView {
+///     view! {
+///         <Suspense fallback=|| "Loading...">
+///             {Suspend::new(children())}
+///         </Suspense>
+///     }
+/// }
+/// ```
+pub type ChildrenAsync = Box<
+    dyn FnOnce() -> Pin<Box<dyn Future<Output = AnyView> + Send>> + Send,
+>;
+
+/// A version of [`ChildrenAsync`] whose future does not need to be `Send`.
+///
+/// Futures that wrap browser APIs, such as a `JsFuture`, are not `Send`, so this is the type to
+/// use for async children in client-side rendering. [`Suspend`](tachys::reactive_graph::Suspend)
+/// requires a `Send` future, so to await the children inside one, wrap the future in a
+/// `SendWrapper` from the `send_wrapper` crate. This is fine in the browser, where everything runs
+/// on the same thread, but the wrapper panics if the future is polled from another thread.
+pub type ChildrenAsyncLocal =
+    Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = AnyView>>> + Send>;
+
+/// A type for the `children` property on components that can be called more than once, but are
+/// not `Sync`.
+///
+/// This is to still support components that accept `Box<dyn Fn() -> AnyView>` as a children.
+pub type BoxedChildrenFn = Box<dyn Fn() -> AnyView + Send>;
+
+/// Returns a view that renders nothing.
+///
+/// This is the view used by the default values of [`ViewFn`] and the other view function types.
+pub fn empty_view() -> AnyView {
+    ().into_any()
+}
+
+/// Wraps a function that returns a view, so that it returns an [`AnyView`] instead.
+///
+/// This is how the children and view function types in this module erase the type of the view
+/// returned by a closure. See [`into_any_fn_once`] for functions that can only be called once,
+/// and [`into_any_fn_mut`] for functions that mutate their captured state.
+pub fn into_any_fn<F, C>(f: F) -> impl Fn() -> AnyView
+where
+    F: Fn() -> C,
+    C: RenderHtml + Send + 'static,
+{
+    move || f().into_any()
+}
+
+/// Wraps a function that returns a view and can only be called once, so that it returns an
+/// [`AnyView`] instead.
+///
+/// See [`into_any_fn`].
+pub fn into_any_fn_once<F, C>(f: F) -> impl FnOnce() -> AnyView
+where
+    F: FnOnce() -> C,
+    C: RenderHtml + Send + 'static,
+{
+    move || f().into_any()
+}
+
+/// Wraps a function that returns a view and may mutate its captured state, so that it returns an
+/// [`AnyView`] instead.
+///
+/// See [`into_any_fn`].
+pub fn into_any_fn_mut<F, C>(mut f: F) -> impl FnMut() -> AnyView
+where
+    F: FnMut() -> C,
+    C: RenderHtml + Send + 'static,
+{
+    move || f().into_any()
+}
+
+/// Returns [`Children`] that render nothing.
+pub fn empty_children() -> Children {
+    Box::new(empty_view)
+}
 
 /// This trait can be used when constructing a component that takes children without needing
 /// to know exactly what children type the component expects. This is used internally by the
@@ -49,6 +176,11 @@ type BoxedChildrenFn = Box<dyn Fn() -> AnyView + Send>;
 /// be directly constructed. Using `ToChildren` allows the component user to pass children without
 /// explicity constructing the correct type.
 ///
+/// Other crates can implement `ToChildren` for their own children types. The `view!` macro passes
+/// the children of a component to `ToChildren::to_children` as a closure, so implementing
+/// `ToChildren<F>` for a local type, with `F` bounded by `FnOnce() -> C` (or `Fn`/`FnMut`), is
+/// enough for that type to be used as a `children` prop.
+///
 /// ## Examples
 ///
 /// ```
@@ -89,6 +221,47 @@ pub trait ToChildren<F> {
     /// e.g., [Children]). See the implementations to see exactly which input types are supported
     /// and which "children" type they are converted to.
     fn to_children(f: F) -> Self;
+
+    /// Like [`ToChildren::to_children`], but also passes the number of top-level children that
+    /// were declared. This is what the `view!` macro calls.
+    ///
+    /// Children types that keep track of this number, such as [`TypedChildren`], use it to fill in
+    /// their metadata. By default, the count is ignored.
+    #[track_caller]
+    fn to_children_with_count(f: F, count: usize) -> Self
+    where
+        Self: Sized,
+    {
+        _ = count;
+        Self::to_children(f)
+    }
+}
+
+/// Creates children for a component from `view!` markup, for use with the builder syntax.
+///
+/// `children! { ... }` expands to `ToChildren::to_children(move || view! { ... })`, so the exact
+/// children type is inferred from the component's `children` prop.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// use leptos::control_flow::{Show, ShowProps};
+///
+/// # fn app() -> impl IntoView {
+/// Show(
+///     ShowProps::builder()
+///         .children(leptos::children! { <p>"Foo"</p> })
+///         .when(|| true)
+///         .build(),
+/// )
+/// # }
+/// ```
+#[macro_export]
+macro_rules! children {
+    ($($tt:tt)*) => {
+        $crate::children::ToChildren::to_children(
+            move || $crate::view! { $($tt)* }
+        )
+    };
 }
 
 impl<F, C> ToChildren<F> for Children
@@ -98,7 +271,7 @@ where
 {
     #[inline]
     fn to_children(f: F) -> Self {
-        Box::new(move || f().into_any())
+        Box::new(into_any_fn_once(f))
     }
 }
 
@@ -109,18 +282,185 @@ where
 {
     #[inline]
     fn to_children(f: F) -> Self {
-        Arc::new(move || f().into_any())
+        Arc::new(into_any_fn(f))
+    }
+}
+
+impl<F, C> ToChildren<Option<F>> for Children
+where
+    F: FnOnce() -> C + Send + 'static,
+    C: RenderHtml + Send + 'static,
+{
+    #[inline]
+    fn to_children(f: Option<F>) -> Self {
+        match f {
+            Some(f) => <Children as ToChildren<F>>::to_children(f),
+            None => empty_children(),
+        }
+    }
+}
+
+impl<F, C> ToChildren<Option<F>> for ChildrenFn
+where
+    F: Fn() -> C + Send + Sync + 'static,
+    C: RenderHtml + Send + 'static,
+{
+    #[inline]
+    fn to_children(f: Option<F>) -> Self {
+        match f {
+            Some(f) => <ChildrenFn as ToChildren<F>>::to_children(f),
+            None => Arc::new(empty_view),
+        }
+    }
+}
+
+impl ToChildren<&'static str> for Children {
+    #[inline]
+    fn to_children(text: &'static str) -> Self {
+        Box::new(into_any_fn_once(move || text))
+    }
+}
+
+impl ToChildren<&'static str> for ChildrenFn {
+    #[inline]
+    fn to_children(text: &'static str) -> Self {
+        Arc::new(into_any_fn(move || text))
+    }
+}
+
+impl ToChildren<String> for Children {
+    #[inline]
+    fn to_children(text: String) -> Self {
+        Box::new(into_any_fn_once(move || text))
+    }
+}
+
+impl ToChildren<String> for ChildrenFn {
+    /// The string is cloned each time the children are rendered.
+    #[inline]
+    fn to_children(text: String) -> Self {
+        Arc::new(into_any_fn(move || text.clone()))
+    }
+}
+
+impl ToChildren<Cow<'static, str>> for Children {
+    #[inline]
+    fn to_children(text: Cow<'static, str>) -> Self {
+        Box::new(into_any_fn_once(move || text))
+    }
+}
+
+impl ToChildren<Cow<'static, str>> for ChildrenFn {
+    /// The string is cloned each time the children are rendered, which only allocates if it is
+    /// owned.
+    #[inline]
+    fn to_children(text: Cow<'static, str>) -> Self {
+        Arc::new(into_any_fn(move || text.clone()))
+    }
+}
+
+impl<V> ToChildren<Vec<V>> for Children
+where
+    V: IntoView + 'static,
+{
+    #[inline]
+    fn to_children(views: Vec<V>) -> Self {
+        Box::new(into_any_fn_once(move || views))
+    }
+}
+
+impl<V, const N: usize> ToChildren<[V; N]> for Children
+where
+    V: IntoView + 'static,
+{
+    #[inline]
+    fn to_children(views: [V; N]) -> Self {
+        Box::new(into_any_fn_once(move || views))
+    }
+}
+
+// `AnyView` is not `Clone`, so an already-built view can only be used for children that are
+// called once.
+impl ToChildren<AnyView> for Children {
+    #[inline]
+    fn to_children(view: AnyView) -> Self {
+        Box::new(move || view)
     }
 }
 
+macro_rules! children_tuples {
+    ($($f:ident => $c:ident),*) => {
+        impl<$($f, $c),*> ToChildren<($($f,)*)> for Children
+        where
+            $($f: FnOnce() -> $c + Send + 'static,
+            $c: RenderHtml + Send + 'static,)*
+        {
+            #[inline]
+            fn to_children(f: ($($f,)*)) -> Self {
+                #[allow(non_snake_case)]
+                let ($($f,)*) = f;
+                Box::new(into_any_fn_once(move || ($($f(),)*)))
+            }
+        }
+    };
+}
+
+children_tuples!(F0 => C0, F1 => C1);
+children_tuples!(F0 => C0, F1 => C1, F2 => C2);
+children_tuples!(F0 => C0, F1 => C1, F2 => C2, F3 => C3);
+children_tuples!(F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4);
+children_tuples!(F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5);
+children_tuples!(
+    F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5, F6 => C6
+);
+children_tuples!(
+    F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5, F6 => C6,
+    F7 => C7
+);
+children_tuples!(
+    F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5, F6 => C6,
+    F7 => C7, F8 => C8
+);
+children_tuples!(
+    F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5, F6 => C6,
+    F7 => C7, F8 => C8, F9 => C9
+);
+children_tuples!(
+    F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5, F6 => C6,
+    F7 => C7, F8 => C8, F9 => C9, F10 => C10
+);
+children_tuples!(
+    F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5, F6 => C6,
+    F7 => C7, F8 => C8, F9 => C9, F10 => C10, F11 => C11
+);
+children_tuples!(
+    F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5, F6 => C6,
+    F7 => C7, F8 => C8, F9 => C9, F10 => C10, F11 => C11, F12 => C12
+);
+children_tuples!(
+    F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5, F6 => C6,
+    F7 => C7, F8 => C8, F9 => C9, F10 => C10, F11 => C11, F12 => C12,
+    F13 => C13
+);
+children_tuples!(
+    F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5, F6 => C6,
+    F7 => C7, F8 => C8, F9 => C9, F10 => C10, F11 => C11, F12 => C12,
+    F13 => C13, F14 => C14
+);
+children_tuples!(
+    F0 => C0, F1 => C1, F2 => C2, F3 => C3, F4 => C4, F5 => C5, F6 => C6,
+    F7 => C7, F8 => C8, F9 => C9, F10 => C10, F11 => C11, F12 => C12,
+    F13 => C13, F14 => C14, F15 => C15
+);
+
 impl<F, C> ToChildren<F> for ChildrenFnMut
 where
-    F: Fn() -> C + Send + 'static,
+    F: FnMut() -> C + Send + 'static,
     C: RenderHtml + Send + 'static,
 {
     #[inline]
     fn to_children(f: F) -> Self {
-        Box::new(move || f().into_any())
+        Box::new(into_any_fn_mut(f))
     }
 }
 
@@ -131,7 +471,7 @@ where
 {
     #[inline]
     fn to_children(f: F) -> Self {
-        Box::new(move || f().into_any())
+        Box::new(into_any_fn(f))
     }
 }
 
@@ -168,138 +508,1845 @@ where
     }
 }
 
-/// New-type wrapper for a function that returns a view with `From` and `Default` traits implemented
-/// to enable optional props in for example `<Show>` and `<Suspense>`.
-#[derive(Clone)]
-pub struct ViewFn(Arc<dyn Fn() -> AnyView + Send + Sync + 'static>);
+impl<F, C, E> ToChildren<F> for FallibleChildren
+where
+    F: FnOnce() -> Result<C, E> + Send + 'static,
+    C: RenderHtml + Send + 'static,
+    E: Into<AnyError>,
+{
+    #[inline]
+    fn to_children(f: F) -> Self {
+        Box::new(move || f().map(IntoAny::into_any).map_err(Into::into))
+    }
+}
 
-impl Default for ViewFn {
-    fn default() -> Self {
-        Self(Arc::new(|| ().into_any()))
+impl<F, Fut, C> ToChildren<F> for ChildrenAsync
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = C> + Send + 'static,
+    C: IntoView + 'static,
+{
+    #[inline]
+    fn to_children(f: F) -> Self {
+        Box::new(move || -> Pin<Box<dyn Future<Output = AnyView> + Send>> {
+            let fut = f();
+            Box::pin(async move { fut.await.into_any() })
+        })
     }
 }
 
-impl<F, C> From<F> for ViewFn
+impl<F, Fut, C> ToChildren<F> for ChildrenAsyncLocal
 where
-    F: Fn() -> C + Send + Sync + 'static,
-    C: RenderHtml + Send + 'static,
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = C> + 'static,
+    C: IntoView + 'static,
 {
-    fn from(value: F) -> Self {
-        Self(Arc::new(move || value().into_any()))
+    #[inline]
+    fn to_children(f: F) -> Self {
+        Box::new(move || -> Pin<Box<dyn Future<Output = AnyView>>> {
+            let fut = f();
+            Box::pin(async move { fut.await.into_any() })
+        })
     }
 }
 
-impl ViewFn {
-    /// Execute the wrapped function
-    pub fn run(&self) -> AnyView {
-        (self.0)()
+/// Extension methods for [`Children`].
+pub trait ChildrenExt {
+    /// Converts these children into [`ChildrenFn`].
+    ///
+    /// This is only safe when you know the resulting function will be called once: because the
+    /// original children can only be called once, calling the returned function a second time
+    /// will panic.
+    fn into_children_fn(self) -> ChildrenFn;
+
+    /// Returns new children that pass the view produced by these children through `f`.
+    ///
+    /// `f` is only called when the returned children are rendered.
+    fn map_view<F, V>(self, f: F) -> Children
+    where
+        F: FnOnce(AnyView) -> V + Send + 'static,
+        V: RenderHtml + Send + 'static;
+
+    /// Returns new children that render the view returned by `f` before these children.
+    ///
+    /// `f` is only called when the returned children are rendered.
+    fn prepend<F, V>(self, f: F) -> Children
+    where
+        F: FnOnce() -> V + Send + 'static,
+        V: RenderHtml + Send + 'static;
+
+    /// Returns new children that render the view returned by `f` after these children.
+    ///
+    /// `f` is only called when the returned children are rendered.
+    fn append<F, V>(self, f: F) -> Children
+    where
+        F: FnOnce() -> V + Send + 'static,
+        V: RenderHtml + Send + 'static;
+}
+
+impl ChildrenExt for Children {
+    fn into_children_fn(self) -> ChildrenFn {
+        let children = Mutex::new(Some(self));
+        Arc::new(move || {
+            let children = children.lock().or_poisoned().take().expect(
+                "`Children` converted into `ChildrenFn` can only be called \
+                 once",
+            );
+            children()
+        })
+    }
+
+    fn map_view<F, V>(self, f: F) -> Children
+    where
+        F: FnOnce(AnyView) -> V + Send + 'static,
+        V: RenderHtml + Send + 'static,
+    {
+        Box::new(into_any_fn_once(move || f(self())))
+    }
+
+    fn prepend<F, V>(self, f: F) -> Children
+    where
+        F: FnOnce() -> V + Send + 'static,
+        V: RenderHtml + Send + 'static,
+    {
+        Box::new(into_any_fn_once(move || (f(), self())))
+    }
+
+    fn append<F, V>(self, f: F) -> Children
+    where
+        F: FnOnce() -> V + Send + 'static,
+        V: RenderHtml + Send + 'static,
+    {
+        Box::new(into_any_fn_once(move || (self(), f())))
     }
 }
 
-/// New-type wrapper for a function, which will only be called once and returns a view with `From` and
-/// `Default` traits implemented to enable optional props in for example `<Show>` and `<Suspense>`.
-pub struct ViewFnOnce(Box<dyn FnOnce() -> AnyView + Send + 'static>);
+/// Extension methods for [`ChildrenFn`].
+pub trait ChildrenFnExt {
+    /// Converts these children into [`Children`], which can only be called once.
+    fn into_children(self) -> Children;
 
-impl Default for ViewFnOnce {
-    fn default() -> Self {
-        Self(Box::new(|| ().into_any()))
+    /// Wraps these children so that they count how many times they are rendered.
+    ///
+    /// Returns the wrapped children, along with a counter that is incremented every time they are
+    /// called. This adds an atomic increment to each render, and is meant for debugging
+    /// over-rendering in development builds.
+    fn instrumented(self) -> (ChildrenFn, Arc<AtomicUsize>);
+
+    /// Wraps these children so that they render nothing unless `predicate` returns `true`.
+    ///
+    /// The predicate is called each time the children are rendered, so reactive values read inside
+    /// it are tracked by whatever is rendering the children.
+    fn guarded<P>(self, predicate: P) -> ChildrenFn
+    where
+        P: Fn() -> bool + Send + Sync + 'static;
+
+    /// Wraps these children so that `value` is provided as context every time they are rendered.
+    ///
+    /// Each render provides a clone of `value` to its own owner, as with [`ViewFn::run_isolated`].
+    fn in_context<T>(self, value: T) -> ChildrenFn
+    where
+        T: Clone + Send + Sync + 'static;
+
+    /// Converts these children into [`CloneableChildren`], to render them in several places.
+    ///
+    /// This reuses the same `Arc`, so cloning the result is cheap.
+    fn share(self) -> CloneableChildren;
+
+    /// Creates a [`WeakChildrenFn`] that refers to these children without keeping them alive.
+    fn downgrade(&self) -> WeakChildrenFn;
+}
+
+impl ChildrenFnExt for ChildrenFn {
+    fn into_children(self) -> Children {
+        Box::new(move || self())
+    }
+
+    fn instrumented(self) -> (ChildrenFn, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let children: ChildrenFn = Arc::new({
+            let count = Arc::clone(&count);
+            move || {
+                count.fetch_add(1, Ordering::Relaxed);
+                self()
+            }
+        });
+        (children, count)
+    }
+
+    fn guarded<P>(self, predicate: P) -> ChildrenFn
+    where
+        P: Fn() -> bool + Send + Sync + 'static,
+    {
+        Arc::new(move || if predicate() { self() } else { empty_view() })
+    }
+
+    fn in_context<T>(self, value: T) -> ChildrenFn
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        Arc::new(move || {
+            let owner = Owner::new();
+            let view = owner.with(|| {
+                provide_context(value.clone());
+                self()
+            });
+            OwnedView::new_with_owner(view, owner).into_any()
+        })
+    }
+
+    fn share(self) -> CloneableChildren {
+        CloneableChildren(self)
+    }
+
+    fn downgrade(&self) -> WeakChildrenFn {
+        WeakChildrenFn(Arc::downgrade(self))
     }
 }
 
-impl<F, C> From<F> for ViewFnOnce
-where
-    F: FnOnce() -> C + Send + 'static,
-    C: RenderHtml + Send + 'static,
-{
-    fn from(value: F) -> Self {
-        Self(Box::new(move || value().into_any()))
+/// A weak reference to some [`ChildrenFn`], created with [`ChildrenFnExt::downgrade`].
+///
+/// This can be held by code that needs to render some children without keeping them alive, for
+/// example to avoid a reference cycle between the children and a signal they capture.
+#[derive(Clone)]
+pub struct WeakChildrenFn(Weak<dyn Fn() -> AnyView + Send + Sync>);
+
+impl Debug for WeakChildrenFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WeakChildrenFn").finish()
     }
 }
 
-impl ViewFnOnce {
-    /// Execute the wrapped function
-    pub fn run(self) -> AnyView {
-        (self.0)()
+impl WeakChildrenFn {
+    /// Returns the children, or `None` if they have already been dropped.
+    pub fn upgrade(&self) -> Option<ChildrenFn> {
+        self.0.upgrade()
+    }
+
+    /// Renders the children, or renders nothing if they have already been dropped.
+    pub fn run(&self) -> AnyView {
+        match self.upgrade() {
+            Some(children) => children(),
+            None => empty_view(),
+        }
     }
 }
 
-/// A typed equivalent to [`Children`], which takes a generic but preserves type information to
-/// allow the compiler to optimize the view more effectively.
-pub struct TypedChildren<T>(Box<dyn FnOnce() -> View<T> + Send>);
+/// Extension methods for [`ChildrenFnMut`].
+pub trait ChildrenFnMutExt {
+    /// Converts these children into [`ChildrenFn`], so they can be shared.
+    ///
+    /// The mutable function is stored behind a [`Mutex`], which is locked for the duration of
+    /// each call, so calls made from different threads at the same time run one after the other.
+    /// Calling the returned function again from inside the children, on the thread that is already
+    /// running them, would deadlock, so it panics instead.
+    fn into_shared(self) -> ChildrenFn;
 
-impl<T> TypedChildren<T> {
-    /// Extracts the inner `children` function.
-    pub fn into_inner(self) -> impl FnOnce() -> View<T> + Send {
-        self.0
+    /// Wraps these children so that they panic if they are called more than `limit` times.
+    ///
+    /// This is meant as a debugging aid for components that assume their mutable children are
+    /// only rendered a bounded number of times.
+    fn with_limit(self, limit: usize) -> ChildrenFnMut;
+}
+
+impl ChildrenFnMutExt for ChildrenFnMut {
+    fn into_shared(self) -> ChildrenFn {
+        let children = Mutex::new(self);
+        let running = Mutex::new(None);
+        Arc::new(move || {
+            let current = thread::current().id();
+            if *running.lock().or_poisoned() == Some(current) {
+                panic!(
+                    "shared `ChildrenFnMut` was called re-entrantly while it \
+                     was already running"
+                );
+            }
+            let mut children = children.lock().or_poisoned();
+            *running.lock().or_poisoned() = Some(current);
+            let _running = RunningGuard(&running);
+            (*children)()
+        })
+    }
+
+    fn with_limit(mut self, limit: usize) -> ChildrenFnMut {
+        let mut calls = 0;
+        Box::new(move || {
+            calls += 1;
+            if calls > limit {
+                panic!(
+                    "`ChildrenFnMut` was called more than {limit} time(s), \
+                     which is its limit"
+                );
+            }
+            self()
+        })
     }
 }
 
-impl<F, C> ToChildren<F> for TypedChildren<C>
-where
-    F: FnOnce() -> C + Send + 'static,
-    C: IntoView,
-    C::AsyncOutput: Send,
-{
-    #[inline]
-    fn to_children(f: F) -> Self {
-        TypedChildren(Box::new(move || f().into_view()))
+/// Clears the thread recorded as running some shared children when they return or panic.
+struct RunningGuard<'a>(&'a Mutex<Option<ThreadId>>);
+
+impl Drop for RunningGuard<'_> {
+    fn drop(&mut self) {
+        *self.0.lock().or_poisoned() = None;
     }
 }
 
-/// A typed equivalent to [`ChildrenMut`], which takes a generic but preserves type information to
-/// allow the compiler to optimize the view more effectively.
-pub struct TypedChildrenMut<T>(Box<dyn FnMut() -> View<T> + Send>);
+/// Extension methods for [`BoxedChildrenFn`].
+pub trait BoxedChildrenFnExt {
+    /// Converts these children into [`Children`], which can only be called once.
+    fn into_children(self) -> Children;
 
-impl<T> Debug for TypedChildrenMut<T> {
+    /// Converts these children into [`ChildrenFn`].
+    ///
+    /// Because the boxed function is not `Sync`, it is shared in the same way as
+    /// [`ChildrenFnMutExt::into_shared`]: calls from different threads wait for each other, and
+    /// calling the returned function again from inside the children panics.
+    fn into_children_fn(self) -> ChildrenFn;
+}
+
+impl BoxedChildrenFnExt for BoxedChildrenFn {
+    fn into_children(self) -> Children {
+        Box::new(move || self())
+    }
+
+    fn into_children_fn(self) -> ChildrenFn {
+        let children: ChildrenFnMut = Box::new(move || self());
+        children.into_shared()
+    }
+}
+
+/// A type for the `children` property on components that can be called more than once, and can
+/// be cloned to render the same children in several places.
+///
+/// Unlike [`Children`], the children must be created by an `Fn` closure.
+#[derive(Clone)]
+pub struct CloneableChildren(Arc<dyn Fn() -> AnyView + Send + Sync>);
+
+impl Debug for CloneableChildren {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("TypedChildrenMut").finish()
+        f.debug_tuple("CloneableChildren").finish()
     }
 }
 
-impl<T> TypedChildrenMut<T> {
+impl CloneableChildren {
+    /// Renders the children.
+    pub fn run(&self) -> AnyView {
+        (self.0)()
+    }
+
     /// Extracts the inner `children` function.
-    pub fn into_inner(self) -> impl FnMut() -> View<T> + Send {
+    pub fn into_inner(self) -> ChildrenFn {
         self.0
     }
 }
 
-impl<F, C> ToChildren<F> for TypedChildrenMut<C>
+impl From<ChildrenFn> for CloneableChildren {
+    fn from(children: ChildrenFn) -> Self {
+        CloneableChildren(children)
+    }
+}
+
+impl From<CloneableChildren> for ChildrenFn {
+    fn from(children: CloneableChildren) -> Self {
+        children.0
+    }
+}
+
+impl<F, C> ToChildren<F> for CloneableChildren
 where
-    F: FnMut() -> C + Send + 'static,
-    C: IntoView,
-    C::AsyncOutput: Send,
+    F: Fn() -> C + Send + Sync + 'static,
+    C: RenderHtml + Send + 'static,
 {
     #[inline]
-    fn to_children(mut f: F) -> Self {
-        TypedChildrenMut(Box::new(move || f().into_view()))
+    fn to_children(f: F) -> Self {
+        CloneableChildren(Arc::new(into_any_fn(f)))
     }
 }
 
-/// A typed equivalent to [`ChildrenFn`], which takes a generic but preserves type information to
-/// allow the compiler to optimize the view more effectively.
-pub struct TypedChildrenFn<T>(Arc<dyn Fn() -> View<T> + Send + Sync>);
+/// A type for the `children` property on components that render their children once per index,
+/// such as a repeater bound to a count.
+///
+/// The children are created from an `Fn(usize)` closure. The `view!` macro always passes a closure
+/// without arguments as children, so this type can only be filled in with the builder syntax,
+/// using [`ToChildren`].
+#[derive(Clone)]
+pub struct IndexedChildren(Arc<dyn Fn(usize) -> AnyView + Send + Sync>);
 
-impl<T> Debug for TypedChildrenFn<T> {
+impl Debug for IndexedChildren {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("TypedChildrenFn").finish()
+        f.debug_tuple("IndexedChildren").finish()
     }
 }
 
-impl<T> TypedChildrenFn<T> {
-    /// Extracts the inner `children` function.
-    pub fn into_inner(self) -> Arc<dyn Fn() -> View<T> + Send + Sync> {
-        self.0
+impl IndexedChildren {
+    /// Renders the children for the given index, under its own owner as with
+    /// [`ViewFn::run_isolated`].
+    pub fn run(&self, index: usize) -> AnyView {
+        let owner = Owner::new();
+        let view = owner.with(|| (self.0)(index));
+        OwnedView::new_with_owner(view, owner).into_any()
     }
 }
 
-impl<F, C> ToChildren<F> for TypedChildrenFn<C>
+impl<F, C> ToChildren<F> for IndexedChildren
 where
-    F: Fn() -> C + Send + Sync + 'static,
-    C: IntoView,
-    C::AsyncOutput: Send,
+    F: Fn(usize) -> C + Send + Sync + 'static,
+    C: RenderHtml + Send + 'static,
 {
     #[inline]
     fn to_children(f: F) -> Self {
-        TypedChildrenFn(Arc::new(move || f().into_view()))
+        IndexedChildren(Arc::new(move |index: usize| f(index).into_any()))
+    }
+}
+
+/// A builder for assembling [`Children`] one view at a time, without the `view!` macro.
+///
+/// Views are rendered in the order in which they were pushed.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos::children::ChildrenBuilder;
+/// # let show_footer = true;
+/// let children = ChildrenBuilder::new()
+///     .push(view! { <h1>"Title"</h1> })
+///     .push_if(show_footer, || view! { <footer>"Footer"</footer> })
+///     .build();
+/// ```
+#[derive(Default)]
+pub struct ChildrenBuilder(Vec<AnyView>);
+
+impl Debug for ChildrenBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ChildrenBuilder").field(&self.0.len()).finish()
+    }
+}
+
+impl ChildrenBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a view after the ones already pushed.
+    pub fn push(mut self, view: impl IntoView + 'static) -> Self {
+        self.0.push(view.into_any());
+        self
+    }
+
+    /// Adds the view returned by `f` if `cond` is `true`. `f` is not called otherwise.
+    pub fn push_if<V>(self, cond: bool, f: impl FnOnce() -> V) -> Self
+    where
+        V: IntoView + 'static,
+    {
+        if cond {
+            self.push(f())
+        } else {
+            self
+        }
+    }
+
+    /// Finishes building, returning [`Children`] that render the pushed views.
+    pub fn build(self) -> Children {
+        Box::new(into_any_fn_once(move || self.0))
+    }
+
+    /// Finishes building, returning a [`ChildrenFragment`] of the pushed views.
+    pub fn build_fragment(self) -> ChildrenFragment {
+        Box::new(move || Fragment::new(self.0))
+    }
+}
+
+/// A type for the `children` property on components that reconcile their children by key.
+///
+/// Each child is paired with a key, so that the component can compare keys between renders and only
+/// rebuild the entries that changed. It can be built with [`ToChildren`] from any iterator of
+/// `(key, view)` pairs, in which case the children are kept in iteration order.
+///
+/// Keys are expected to be unique. If the same key appears more than once, the last entry with that
+/// key wins and earlier ones are dropped. This is checked with a debug assertion, so duplicate keys
+/// panic in debug builds.
+pub struct ChildrenKeyed<K>(Vec<(K, AnyView)>);
+
+impl<K: Debug> Debug for ChildrenKeyed<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ChildrenKeyed")
+            .field(&self.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<K> ChildrenKeyed<K> {
+    /// Returns the number of children.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no children.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the keys of the children, in order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.0.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the keyed children, in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, (K, AnyView)> {
+        self.0.iter()
+    }
+
+    /// Extracts the keyed children.
+    pub fn into_inner(self) -> Vec<(K, AnyView)> {
+        self.0
+    }
+}
+
+impl<K> IntoIterator for ChildrenKeyed<K> {
+    type Item = (K, AnyView);
+    type IntoIter = std::vec::IntoIter<(K, AnyView)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<I, K, V> ToChildren<I> for ChildrenKeyed<K>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Eq + Hash,
+    V: IntoView + 'static,
+{
+    fn to_children(children: I) -> Self {
+        let entries = children
+            .into_iter()
+            .map(|(key, view)| (key, view.into_any()))
+            .collect::<Vec<_>>();
+
+        let mut last_index = HashMap::with_capacity(entries.len());
+        for (idx, (key, _)) in entries.iter().enumerate() {
+            let prev = last_index.insert(key, idx);
+            debug_assert!(prev.is_none(), "duplicate key in `ChildrenKeyed`");
+        }
+        let keep = entries
+            .iter()
+            .enumerate()
+            .map(|(idx, (key, _))| last_index[&key] == idx)
+            .collect::<Vec<_>>();
+
+        ChildrenKeyed(
+            entries
+                .into_iter()
+                .zip(keep)
+                .filter_map(|(entry, keep)| keep.then_some(entry))
+                .collect(),
+        )
+    }
+}
+
+/// A set of named children, for components that render several regions such as a header, a body
+/// and a footer.
+///
+/// Each slot is stored as a [`ChildrenFn`], so it can be rendered any number of times. Slots are
+/// filled in with [`Slots::with`] or [`Slots::insert`]. A component asking for a slot that was not
+/// provided gets `None` back from [`Slots::get`] and [`Slots::take`], and can decide whether to
+/// render a fallback or nothing at all.
+///
+/// A component can take `Slots` as its `children` prop, passed as the value of that prop from the
+/// `view!` macro. They can also be collected from any iterator of `(name, children)` pairs.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos::children::Slots;
+/// #[component]
+/// fn Card(children: Slots) -> impl IntoView {
+///     view! {
+///         <header>{children.get("header")}</header>
+///         <main>{children.get("body")}</main>
+///     }
+/// }
+///
+/// # fn app() -> impl IntoView {
+/// view! {
+///     <Card children={
+///         Slots::new()
+///             .with("header", || view! { <h1>"Title"</h1> })
+///             .with("body", || "Content")
+///     }/>
+/// }
+/// # }
+/// ```
+///
+/// For slots that are known at compile time and filled in from child elements in the `view!`
+/// macro, see the `#[slot]` attribute macro.
+#[derive(Clone, Default)]
+pub struct Slots(HashMap<&'static str, ChildrenFn>);
+
+impl Debug for Slots {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Slots")
+            .field(&self.0.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Slots {
+    /// Creates an empty set of slots.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fills in the slot `name`, replacing any children it already had.
+    pub fn with<F>(mut self, name: &'static str, children: F) -> Self
+    where
+        ChildrenFn: ToChildren<F>,
+    {
+        let children = <ChildrenFn as ToChildren<F>>::to_children(children);
+        self.insert(name, children);
+        self
+    }
+
+    /// Fills in the slot `name`, returning the children it previously had, if any.
+    pub fn insert(
+        &mut self,
+        name: &'static str,
+        children: ChildrenFn,
+    ) -> Option<ChildrenFn> {
+        self.0.insert(name, children)
+    }
+
+    /// Returns `true` if the slot `name` was provided.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    /// Renders the slot `name`, or returns `None` if it was not provided.
+    pub fn get(&self, name: &str) -> Option<AnyView> {
+        self.0.get(name).map(|children| children())
+    }
+
+    /// Removes the slot `name` and returns its children, or `None` if it was not provided.
+    pub fn take(&mut self, name: &str) -> Option<ChildrenFn> {
+        self.0.remove(name)
+    }
+
+    /// Returns an iterator over the names of the provided slots, in arbitrary order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.0.keys().copied()
+    }
+}
+
+impl FromIterator<(&'static str, ChildrenFn)> for Slots {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (&'static str, ChildrenFn)>,
+    {
+        Slots(iter.into_iter().collect())
+    }
+}
+
+impl Extend<(&'static str, ChildrenFn)> for Slots {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (&'static str, ChildrenFn)>,
+    {
+        self.0.extend(iter);
+    }
+}
+
+/// New-type wrapper for a function that returns a view with `From` and `Default` traits implemented
+/// to enable optional props in for example `<Show>` and `<Suspense>`.
+///
+/// Wrappers that only change how the function runs, like [`ViewFn::with_owner`],
+/// [`ViewFn::catch_unwind`], [`ViewFn::filter`] and [`ViewFn::timed`], are still the default or a
+/// no-op if the original was, and [`ViewFn::chain`] is if both were. [`ViewFn::map`] is neither,
+/// since `f` can render something from nothing.
+#[derive(Clone)]
+pub struct ViewFn {
+    f: Arc<dyn Fn() -> AnyView + Send + Sync + 'static>,
+    is_default: bool,
+    is_noop: bool,
+}
+
+impl Debug for ViewFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ViewFn").finish_non_exhaustive()
+    }
+}
+
+impl Default for ViewFn {
+    fn default() -> Self {
+        Self {
+            f: Arc::new(empty_view),
+            is_default: true,
+            is_noop: false,
+        }
+    }
+}
+
+impl From<()> for ViewFn {
+    fn from(_: ()) -> Self {
+        Self::default()
+    }
+}
+
+impl<F, C> From<F> for ViewFn
+where
+    F: Fn() -> C + Send + Sync + 'static,
+    C: RenderHtml + Send + 'static,
+{
+    fn from(value: F) -> Self {
+        Self {
+            f: Arc::new(into_any_fn(value)),
+            is_default: false,
+            is_noop: false,
+        }
+    }
+}
+
+impl<F, C> ToChildren<F> for ViewFn
+where
+    F: Fn() -> C + Send + Sync + 'static,
+    C: RenderHtml + Send + 'static,
+{
+    #[inline]
+    fn to_children(f: F) -> Self {
+        ViewFn::from(f)
+    }
+}
+
+impl ViewFn {
+    /// Execute the wrapped function
+    ///
+    /// Each call runs the function again and returns a new view, which does not share any nodes
+    /// with views returned by earlier calls. Reactive work created while rendering belongs to the
+    /// current reactive owner; see [`ViewFn::run_isolated`] to give each call its own.
+    pub fn run(&self) -> AnyView {
+        (self.f)()
+    }
+
+    /// Execute the wrapped function under a new reactive [`Owner`], a child of the current one.
+    ///
+    /// The owner lives as long as the returned view, so renders in different places stay apart.
+    pub fn run_isolated(&self) -> AnyView {
+        let owner = Owner::new();
+        let view = owner.with(|| self.run());
+        OwnedView::new_with_owner(view, owner).into_any()
+    }
+
+    /// Returns `other` if this is the default [`ViewFn`], and `self` otherwise.
+    ///
+    /// The output of a function cannot be inspected to see whether it is empty, so this only
+    /// falls back when `self` was created with [`ViewFn::default`]. A function that happens to
+    /// render nothing (for example, `|| ()`) is kept.
+    pub fn or(self, other: ViewFn) -> ViewFn {
+        if self.is_default {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Creates a [`ViewFn`] that explicitly renders nothing.
+    ///
+    /// This renders the same empty view as [`ViewFn::default`], but is not treated as the default:
+    /// [`ViewFn::or`] and [`ViewFn::run_or`] keep it rather than falling back, and
+    /// [`ViewFn::is_noop`] returns `true`. A component can use this to skip rendering a wrapper
+    /// element around content that was intentionally left empty.
+    pub fn noop() -> ViewFn {
+        Self {
+            f: Arc::new(empty_view),
+            is_default: false,
+            is_noop: true,
+        }
+    }
+
+    /// Returns `true` if this [`ViewFn`] was created with [`ViewFn::noop`].
+    ///
+    /// The default [`ViewFn`] is not a no-op: it means that no content was provided at all.
+    pub fn is_noop(&self) -> bool {
+        self.is_noop
+    }
+
+    /// Runs the wrapped function, or `fallback` if this is the default [`ViewFn`].
+    ///
+    /// As with [`ViewFn::or`], the fallback is only used when this `ViewFn` was created with
+    /// [`ViewFn::default`].
+    pub fn run_or<F, C>(&self, fallback: F) -> AnyView
+    where
+        F: FnOnce() -> C,
+        C: RenderHtml + Send + 'static,
+    {
+        if self.is_default {
+            fallback().into_any()
+        } else {
+            self.run()
+        }
+    }
+
+    /// Returns a new [`ViewFn`] that applies `f` to the view produced by this one.
+    ///
+    /// `f` is called every time the new function is run, so any reactive values read inside it
+    /// are tracked just like values read in the original function. The original function runs
+    /// first, under the same reactive owner, so `f` can also embed its view in a larger structure
+    /// that shares the same context:
+    ///
+    /// ```
+    /// # use leptos::prelude::*;
+    /// let content = ViewFn::from(|| view! { <p>"Content"</p> });
+    /// let card = content.map(|content| view! { <div class="card">{content}</div> });
+    /// ```
+    pub fn map<F, C>(self, f: F) -> ViewFn
+    where
+        F: Fn(AnyView) -> C + Send + Sync + 'static,
+        C: RenderHtml + Send + 'static,
+    {
+        ViewFn::from(move || f(self.run()))
+    }
+
+    /// Returns a new [`ViewFn`] that renders `on_panic` if running this one panics.
+    ///
+    /// Only panics that unwind while the function runs are caught, not those raised while the view
+    /// is built or updated, and state shared with the rest of the app may be left inconsistent.
+    pub fn catch_unwind(self, on_panic: ViewFn) -> ViewFn {
+        let flags = self.flags();
+        ViewFn::from(move || {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                self.run()
+            }))
+            .unwrap_or_else(|_| on_panic.run())
+        })
+        .with_flags(flags)
+    }
+
+    /// Returns a new [`ViewFn`] that renders nothing unless `pred` returns `true`.
+    ///
+    /// The predicate is called each time the function is run, so reactive values read inside it
+    /// are tracked by whatever is rendering the view. See also [`ChildrenFnExt::guarded`].
+    pub fn filter<P>(self, pred: P) -> ViewFn
+    where
+        P: Fn() -> bool + Send + Sync + 'static,
+    {
+        let flags = self.flags();
+        ViewFn::from(move || if pred() { self.run() } else { empty_view() })
+            .with_flags(flags)
+    }
+
+    /// Runs the function `n` times with [`ViewFn::run_isolated`], collecting the views into a
+    /// [`Fragment`]. To render a list that changes over time, use `<For/>` instead.
+    pub fn repeated(&self, n: usize) -> Fragment {
+        (0..n).map(|_| self.run_isolated()).collect()
+    }
+
+    /// Returns a new [`ViewFn`] that renders this view followed by `other`.
+    ///
+    /// Both functions are run every time the new function is run, in that order, so both stay
+    /// reactive.
+    pub fn chain(self, other: ViewFn) -> ViewFn {
+        let flags = (
+            self.is_default && other.is_default,
+            self.is_noop && other.is_noop,
+        );
+        ViewFn::from(move || (self.run(), other.run())).with_flags(flags)
+    }
+
+    /// Creates a [`ViewFn`] from a view that has already been built.
+    ///
+    /// The view is cloned each time the function is run.
+    pub fn from_view<V>(view: V) -> ViewFn
+    where
+        V: RenderHtml + Clone + Send + Sync + 'static,
+    {
+        ViewFn::from(move || view.clone())
+    }
+
+    /// Creates a [`ViewFn`] that renders the current value of the given signal.
+    ///
+    /// The signal is read inside the function, so it is tracked by whatever is rendering the
+    /// view and the view updates when the signal changes.
+    pub fn from_signal<S>(signal: S) -> ViewFn
+    where
+        S: Get + Send + Sync + 'static,
+        S::Value: IntoView + 'static,
+    {
+        ViewFn::from(move || signal.get())
+    }
+
+    /// Creates a [`ViewFn`] that renders the current value of the given [`Memo`].
+    ///
+    /// This works like [`ViewFn::from_signal`]. Because a memo only notifies its subscribers when
+    /// its value has actually changed, the view is only rebuilt when the new value differs from the
+    /// previous one.
+    pub fn from_memo<V>(memo: Memo<V>) -> ViewFn
+    where
+        V: IntoView + Clone + Send + Sync + 'static,
+    {
+        ViewFn::from_signal(memo)
+    }
+
+    /// Creates a [`ViewFn`] that renders the value of the given [`Resource`].
+    ///
+    /// The resource is read inside the function, and `f` is called with `None` while it is
+    /// loading and with `Some` once it has resolved. The view updates whenever the resource does.
+    ///
+    /// Reading the resource registers it with the nearest enclosing `<Suspense/>`, if any, so that
+    /// boundary shows its fallback until the resource has resolved, and during server rendering
+    /// waits for it before sending this view. Without a `<Suspense/>`, the view produced
+    /// from `None` is shown until the value has loaded.
+    pub fn from_resource<T, Ser, F, V>(
+        resource: Resource<T, Ser>,
+        f: F,
+    ) -> ViewFn
+    where
+        T: Clone + Send + Sync + 'static,
+        Ser: Send + Sync + 'static,
+        F: Fn(Option<T>) -> V + Send + Sync + 'static,
+        V: IntoView + 'static,
+    {
+        ViewFn::from(move || f(resource.get()))
+    }
+
+    /// Creates a [`ViewFn`] that renders `placeholder` until a future has resolved, then renders
+    /// its output.
+    ///
+    /// Each time the function is run, it calls `fut` and spawns the future with
+    /// [`spawn_local`](crate::task::spawn_local), and creates a new signal that the future writes
+    /// its output to. The returned view reads that signal, so it switches from the placeholder to
+    /// the output once the future has resolved. The signal lives as long as the view and the task,
+    /// whichever lasts longer: if the view is dropped first, the task still runs to completion but
+    /// its output is discarded.
+    ///
+    /// This is meant for client-side rendering. With the `ssr` feature enabled, `fut` is never
+    /// called and no task is spawned, because its output could not be sent to the client: only
+    /// the placeholder is rendered. Use a [`Resource`] with `<Suspense/>` for content that should
+    /// be loaded on the server.
+    pub fn from_async<P, PV, F, Fut, V>(placeholder: P, fut: F) -> ViewFn
+    where
+        P: Fn() -> PV + Send + Sync + 'static,
+        PV: IntoView + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = V> + 'static,
+        V: IntoView + Clone + Send + Sync + 'static,
+    {
+        let placeholder = Arc::new(placeholder);
+        ViewFn::from(move || {
+            let value = ArcRwSignal::<Option<V>>::new(None);
+            #[cfg(not(feature = "ssr"))]
+            crate::task::spawn_local({
+                use reactive_graph::traits::Set;
+
+                let value = value.clone();
+                let fut = fut();
+                async move {
+                    value.set(Some(fut.await));
+                }
+            });
+            #[cfg(feature = "ssr")]
+            _ = &fut;
+            let placeholder = Arc::clone(&placeholder);
+            move || match value.get() {
+                Some(value) => Either::Left(value),
+                None => Either::Right(placeholder()),
+            }
+        })
+    }
+
+    /// Returns a new [`ViewFn`] that runs this one with the given [`Owner`] set as the current
+    /// reactive owner.
+    ///
+    /// This lets a function rendered later, outside of the scope that created it, read that
+    /// scope's context for as long as the owner has not been cleaned up.
+    pub fn with_owner(self, owner: Owner) -> ViewFn {
+        let flags = self.flags();
+        ViewFn::from(move || owner.with(|| self.run())).with_flags(flags)
+    }
+
+    /// Returns a new [`ViewFn`] that measures how long this one takes to run.
+    ///
+    /// Each run is reported to the profiler set with [`set_view_fn_profiler`], along with `label`.
+    /// Without the `profiling` feature, or on `wasm32-unknown-unknown`, `self` is returned as is.
+    pub fn timed(self, label: &'static str) -> ViewFn {
+        #[cfg(all(
+            feature = "profiling",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ))]
+        {
+            let flags = self.flags();
+            ViewFn::from(move || {
+                let start = std::time::Instant::now();
+                let view = self.run();
+                let profiler = *VIEW_FN_PROFILER.read().or_poisoned();
+                if let Some(profiler) = profiler {
+                    profiler(label, start.elapsed());
+                }
+                view
+            })
+            .with_flags(flags)
+        }
+        #[cfg(not(all(
+            feature = "profiling",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        )))]
+        {
+            _ = label;
+            self
+        }
+    }
+
+    /// Returns the `is_default` and `is_noop` flags, to copy onto a wrapper with
+    /// [`ViewFn::with_flags`].
+    fn flags(&self) -> (bool, bool) {
+        (self.is_default, self.is_noop)
+    }
+
+    /// Replaces the `is_default` and `is_noop` flags.
+    fn with_flags(mut self, (is_default, is_noop): (bool, bool)) -> ViewFn {
+        self.is_default = is_default;
+        self.is_noop = is_noop;
+        self
+    }
+
+    /// Returns `true` if both values wrap the same function.
+    ///
+    /// This compares identity, not what the functions render: two `ViewFn`s created from
+    /// identical closures are not equal. It is only useful when the same `ViewFn` is reused across
+    /// renders, for example by storing it in a signal, and cloned from there.
+    pub fn ptr_eq(&self, other: &ViewFn) -> bool {
+        Arc::ptr_eq(&self.f, &other.f)
+    }
+
+    /// Creates a [`ViewFn`] from a function that has already been wrapped in an [`Arc`].
+    pub fn from_arc(
+        f: Arc<dyn Fn() -> AnyView + Send + Sync + 'static>,
+    ) -> Self {
+        Self {
+            f,
+            is_default: false,
+            is_noop: false,
+        }
+    }
+
+    /// Extracts the inner function.
+    pub fn into_arc(self) -> Arc<dyn Fn() -> AnyView + Send + Sync + 'static> {
+        self.f
+    }
+
+    /// Converts this function into [`ChildrenFn`].
+    ///
+    /// Both wrap an `Arc`'d function, so the underlying `Arc` is reused rather than wrapped again.
+    pub fn into_children_fn(self) -> ChildrenFn {
+        self.f
+    }
+
+    /// Converts this function into [`Children`], which can only be called once.
+    pub fn into_children(self) -> Children {
+        Box::new(move || self.run())
+    }
+}
+
+/// Creates a [`ViewFn`] from `view!` markup.
+///
+/// `view_fn! { ... }` expands to `ViewFn::from(move || view! { ... })`. The markup is passed to
+/// `view!` unchanged, so reactive values read inside it are tracked as usual.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// use leptos::control_flow::{Show, ShowProps};
+///
+/// # fn app() -> impl IntoView {
+/// Show(
+///     ShowProps::builder()
+///         .children(leptos::children! { <p>"Foo"</p> })
+///         .when(|| false)
+///         .fallback(leptos::view_fn! { <p>"Loading..."</p> })
+///         .build(),
+/// )
+/// # }
+/// ```
+#[macro_export]
+macro_rules! view_fn {
+    ($($tt:tt)*) => {
+        $crate::children::ViewFn::from(move || $crate::view! { $($tt)* })
+    };
+}
+
+/// A shared set of named [`ViewFn`]s, for theming or plugin systems that resolve views by name
+/// when rendering.
+///
+/// The registry is a cheaply-cloned handle to shared, thread-safe storage, so it can be provided
+/// as context and updated from anywhere that has a clone of it. Registering a view under a name
+/// that is already used replaces the earlier view.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos::children::ViewFnRegistry;
+/// # let owner = Owner::new();
+/// # owner.set();
+/// let registry = ViewFnRegistry::default();
+/// registry.register("header", ViewFn::from(|| view! { <h1>"Title"</h1> }));
+/// provide_context(registry);
+///
+/// // later, in a component
+/// let registry = expect_context::<ViewFnRegistry>();
+/// let header = registry.get("header").unwrap_or_default().run();
+/// ```
+#[derive(Clone, Default)]
+pub struct ViewFnRegistry(Arc<RwLock<HashMap<&'static str, ViewFn>>>);
+
+impl Debug for ViewFnRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ViewFnRegistry")
+            .field(&self.0.read().or_poisoned().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ViewFnRegistry {
+    /// Registers `view_fn` under `name`, returning the view that was previously registered under
+    /// that name, if any.
+    pub fn register(
+        &self,
+        name: &'static str,
+        view_fn: ViewFn,
+    ) -> Option<ViewFn> {
+        self.0.write().or_poisoned().insert(name, view_fn)
+    }
+
+    /// Returns the view registered under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<ViewFn> {
+        self.0.read().or_poisoned().get(name).cloned()
+    }
+
+    /// Removes the view registered under `name`, returning it if there was one.
+    pub fn remove(&self, name: &str) -> Option<ViewFn> {
+        self.0.write().or_poisoned().remove(name)
+    }
+}
+
+/// A version of [`ViewFn`] that does not require the function to be `Send` or `Sync`.
+///
+/// This is useful in client-side-only applications, where an optional view prop may need to
+/// capture values that cannot be sent between threads, such as an `Rc`. Its `From` and `Default`
+/// implementations, and its default and no-op handling, mirror those of [`ViewFn`].
+#[derive(Clone)]
+pub struct ViewFnLocal {
+    f: Rc<dyn Fn() -> AnyView + 'static>,
+    is_default: bool,
+    is_noop: bool,
+}
+
+impl Debug for ViewFnLocal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ViewFnLocal").finish_non_exhaustive()
+    }
+}
+
+impl Default for ViewFnLocal {
+    fn default() -> Self {
+        Self {
+            f: Rc::new(empty_view),
+            is_default: true,
+            is_noop: false,
+        }
+    }
+}
+
+impl<F, C> From<F> for ViewFnLocal
+where
+    F: Fn() -> C + 'static,
+    C: RenderHtml + Send + 'static,
+{
+    fn from(value: F) -> Self {
+        Self {
+            f: Rc::new(into_any_fn(value)),
+            is_default: false,
+            is_noop: false,
+        }
+    }
+}
+
+impl ViewFnLocal {
+    /// Execute the wrapped function
+    pub fn run(&self) -> AnyView {
+        (self.f)()
+    }
+
+    /// Returns `other` if this is the default [`ViewFnLocal`], and `self` otherwise.
+    ///
+    /// See [`ViewFn::or`].
+    pub fn or(self, other: ViewFnLocal) -> ViewFnLocal {
+        if self.is_default {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Creates a [`ViewFnLocal`] that explicitly renders nothing.
+    ///
+    /// See [`ViewFn::noop`].
+    pub fn noop() -> ViewFnLocal {
+        Self {
+            f: Rc::new(empty_view),
+            is_default: false,
+            is_noop: true,
+        }
+    }
+
+    /// Returns `true` if this [`ViewFnLocal`] was created with [`ViewFnLocal::noop`].
+    pub fn is_noop(&self) -> bool {
+        self.is_noop
+    }
+
+    /// Runs the wrapped function, or `fallback` if this is the default [`ViewFnLocal`].
+    ///
+    /// See [`ViewFn::run_or`].
+    pub fn run_or<F, C>(&self, fallback: F) -> AnyView
+    where
+        F: FnOnce() -> C,
+        C: RenderHtml + Send + 'static,
+    {
+        if self.is_default {
+            fallback().into_any()
+        } else {
+            self.run()
+        }
+    }
+}
+
+#[cfg(feature = "profiling")]
+static VIEW_FN_PROFILER: RwLock<Option<fn(&'static str, Duration)>> =
+    RwLock::new(None);
+
+/// Sets the function that [`ViewFn::timed`] reports to, replacing any previous one.
+///
+/// The profiler is called with the label and the time it took to run the [`ViewFn`]. This does
+/// nothing unless the `profiling` feature is enabled.
+pub fn set_view_fn_profiler(profiler: fn(&'static str, Duration)) {
+    #[cfg(feature = "profiling")]
+    {
+        *VIEW_FN_PROFILER.write().or_poisoned() = Some(profiler);
+    }
+    #[cfg(not(feature = "profiling"))]
+    {
+        _ = profiler;
+    }
+}
+
+/// New-type wrapper for a function, which will only be called once and returns a view with `From` and
+/// `Default` traits implemented to enable optional props in for example `<Show>` and `<Suspense>`.
+pub struct ViewFnOnce(Box<dyn FnOnce() -> AnyView + Send + 'static>);
+
+impl Debug for ViewFnOnce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ViewFnOnce").finish_non_exhaustive()
+    }
+}
+
+impl Default for ViewFnOnce {
+    fn default() -> Self {
+        Self(Box::new(empty_view))
+    }
+}
+
+impl<F, C> From<F> for ViewFnOnce
+where
+    F: FnOnce() -> C + Send + 'static,
+    C: RenderHtml + Send + 'static,
+{
+    fn from(value: F) -> Self {
+        Self(Box::new(into_any_fn_once(value)))
+    }
+}
+
+impl ViewFnOnce {
+    /// Execute the wrapped function
+    pub fn run(self) -> AnyView {
+        (self.0)()
+    }
+}
+
+/// New-type wrapper for a function, which may mutate its captured state each time it is called and
+/// returns a view with `From` and `Default` traits implemented to enable optional props.
+pub struct ViewFnMut(Box<dyn FnMut() -> AnyView + Send + 'static>);
+
+impl Debug for ViewFnMut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ViewFnMut").finish_non_exhaustive()
+    }
+}
+
+impl Default for ViewFnMut {
+    fn default() -> Self {
+        Self(Box::new(empty_view))
+    }
+}
+
+impl<F, C> From<F> for ViewFnMut
+where
+    F: FnMut() -> C + Send + 'static,
+    C: RenderHtml + Send + 'static,
+{
+    fn from(value: F) -> Self {
+        Self(Box::new(into_any_fn_mut(value)))
+    }
+}
+
+impl ViewFnMut {
+    /// Execute the wrapped function
+    pub fn run(&mut self) -> AnyView {
+        (self.0)()
+    }
+}
+
+/// New-type wrapper for a function that takes an argument and returns a view, with `From` and
+/// `Default` traits implemented to enable optional props.
+///
+/// This can be used for fallbacks that depend on data only known when they are rendered, such as
+/// an error message or a retry count.
+pub struct ViewFnWith<A>(Arc<dyn Fn(A) -> AnyView + Send + Sync + 'static>);
+
+impl<A> Clone for ViewFnWith<A> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<A> Debug for ViewFnWith<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ViewFnWith").finish_non_exhaustive()
+    }
+}
+
+impl<A> Default for ViewFnWith<A> {
+    fn default() -> Self {
+        Self(Arc::new(|_: A| empty_view()))
+    }
+}
+
+impl<F, A, C> From<F> for ViewFnWith<A>
+where
+    F: Fn(A) -> C + Send + Sync + 'static,
+    C: RenderHtml + Send + 'static,
+{
+    fn from(value: F) -> Self {
+        Self(Arc::new(move |arg: A| value(arg).into_any()))
+    }
+}
+
+impl<A> ViewFnWith<A> {
+    /// Execute the wrapped function with the given argument
+    pub fn run(&self, arg: A) -> AnyView {
+        (self.0)(arg)
+    }
+}
+
+/// A typed equivalent to [`ViewFn`], which preserves the type of the view returned by the function.
+///
+/// Like the typed children types, this lets the compiler optimize the rendered view more
+/// effectively, at the cost of an extra generic parameter on the component. It is worth using for
+/// views that are rendered often, or when the component needs the concrete view type; otherwise
+/// [`ViewFn`] is simpler. Use [`TypedViewFn::erase`] to convert it into a [`ViewFn`] where the
+/// type needs to be erased.
+///
+/// Unlike [`ViewFn`], this does not implement `Default`, as there is no default view of type `T`.
+pub struct TypedViewFn<T>(Arc<dyn Fn() -> View<T> + Send + Sync>);
+
+impl<T> Clone for TypedViewFn<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Debug for TypedViewFn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedViewFn")
+            .field(&std::any::type_name::<T>())
+            .finish()
+    }
+}
+
+impl<F, C> From<F> for TypedViewFn<C>
+where
+    F: Fn() -> C + Send + Sync + 'static,
+    C: IntoView,
+    C::AsyncOutput: Send,
+{
+    fn from(value: F) -> Self {
+        Self(Arc::new(move || value().into_view()))
+    }
+}
+
+impl<T> TypedViewFn<T> {
+    /// Execute the wrapped function
+    pub fn run(&self) -> View<T> {
+        (self.0)()
+    }
+
+    /// Returns a new [`TypedViewFn`] that adds `attr` to the view each time it is run.
+    ///
+    /// If the view is a single element, the attribute is added to that element. If it is a tuple
+    /// or a list of views, the attribute is added to each of them in turn. Views that cannot take
+    /// attributes, such as text, are left unchanged.
+    ///
+    /// This is only available before the type of the view has been erased: the attribute cannot
+    /// be added to an [`AnyView`], so there is no equivalent on [`ViewFn`].
+    pub fn with_attr<A>(self, attr: A) -> TypedViewFn<T::Output<A>>
+    where
+        T: AddAnyAttr + 'static,
+        A: Attribute + Clone + Send + Sync + 'static,
+    {
+        TypedViewFn(Arc::new(move || self.run().add_any_attr(attr.clone())))
+    }
+
+    /// Erases the type of the view, converting this into a [`ViewFn`].
+    pub fn erase(self) -> ViewFn
+    where
+        T: RenderHtml + Send + 'static,
+    {
+        ViewFn::from(move || self.run())
+    }
+}
+
+/// A typed equivalent to [`Children`], which takes a generic but preserves type information to
+/// allow the compiler to optimize the view more effectively.
+///
+/// Only [`TypedChildren::try_into_inner`] checks the error from [`TypedChildren::validated`]; the
+/// other accessors ignore it, so check [`TypedChildren::validation_error`] before using them.
+pub struct TypedChildren<T> {
+    children: Box<dyn FnOnce() -> View<T> + Send>,
+    meta: ChildrenMeta,
+    error: Option<AnyError>,
+}
+
+/// Metadata about some [`TypedChildren`], which can be inspected without rendering them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChildrenMeta {
+    /// The source location at which the children were declared, if known.
+    pub location: Option<&'static Location<'static>>,
+    /// The number of top-level children that were declared, if known.
+    ///
+    /// This is filled in by the `view!` macro. Only the children themselves are counted, not
+    /// their descendants, and slots are not counted.
+    pub count: Option<usize>,
+}
+
+impl<T> Debug for TypedChildren<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedChildren")
+            .field(&std::any::type_name::<T>())
+            .field(&self.meta)
+            .field(&self.error)
+            .finish()
+    }
+}
+
+impl<T> TypedChildren<T> {
+    /// Creates typed children from a function that returns a [`View`].
+    ///
+    /// Together with [`TypedChildren::into_parts`] and [`TypedChildren::with_meta`], this lets a
+    /// component forward its children to another component without erasing their type. As with
+    /// children created by the `view!` macro, the location of the call is recorded and logged if
+    /// rendering the children panics.
+    #[track_caller]
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnOnce() -> View<T> + Send + 'static,
+    {
+        let location = Location::caller();
+        TypedChildren {
+            children: Box::new(move || {
+                let _guard = PanicLocationGuard(location);
+                f()
+            }),
+            meta: ChildrenMeta {
+                location: Some(location),
+                count: None,
+            },
+            error: None,
+        }
+    }
+
+    /// Creates typed children from `f`, and runs `validate` immediately.
+    ///
+    /// If `validate` returns an error, it is stored with the children and returned by
+    /// [`TypedChildren::try_into_inner`], so that a component can reject its children without
+    /// panicking.
+    #[track_caller]
+    pub fn validated<F, E>(
+        f: F,
+        validate: impl FnOnce() -> Result<(), E>,
+    ) -> Self
+    where
+        Self: ToChildren<F>,
+        E: Into<AnyError>,
+    {
+        let mut children = Self::to_children(f);
+        children.error = validate().err().map(Into::into);
+        children
+    }
+
+    /// Extracts the inner `children` function.
+    pub fn into_inner(self) -> impl FnOnce() -> View<T> + Send {
+        self.children
+    }
+
+    /// Extracts the inner `children` function, or returns the error from
+    /// [`TypedChildren::validated`] if validation failed.
+    ///
+    /// The error is stored as an [`AnyError`], the error type collected by `<ErrorBoundary/>`,
+    /// so that `TypedChildren` does not need a second type parameter for it. The original error
+    /// can be recovered by downcasting [`AnyError::into_inner`].
+    pub fn try_into_inner(
+        self,
+    ) -> Result<impl FnOnce() -> View<T> + Send, AnyError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.children),
+        }
+    }
+
+    /// Returns the error from [`TypedChildren::validated`], if validation failed.
+    pub fn validation_error(&self) -> Option<&AnyError> {
+        self.error.as_ref()
+    }
+
+    /// Returns the metadata attached to these children.
+    pub fn meta(&self) -> &ChildrenMeta {
+        &self.meta
+    }
+
+    /// Returns the number of top-level children, if known, without rendering them.
+    ///
+    /// The count is known when the children were passed with the `view!` macro; see
+    /// [`ChildrenMeta::count`]. Only siblings at the top level are counted, not their descendants.
+    pub fn count(&self) -> Option<usize> {
+        self.meta.count
+    }
+
+    /// Replaces the metadata attached to these children.
+    pub fn with_meta(mut self, meta: ChildrenMeta) -> Self {
+        self.meta = meta;
+        self
+    }
+
+    /// Splits the inner `children` function from its metadata.
+    pub fn into_parts(
+        self,
+    ) -> (impl FnOnce() -> View<T> + Send, ChildrenMeta) {
+        (self.children, self.meta)
+    }
+
+    /// Renders the children, returning the typed view.
+    pub fn into_view(self) -> View<T> {
+        (self.children)()
+    }
+
+    /// Renders the children and erases the type of the resulting view.
+    pub fn into_any(self) -> AnyView
+    where
+        T: RenderHtml + Send + 'static,
+    {
+        self.into_view().into_any()
+    }
+
+    /// Erases the type of the children, converting them into [`Children`].
+    ///
+    /// The metadata is dropped.
+    pub fn into_erased_once(self) -> Children
+    where
+        T: RenderHtml + Send + 'static,
+    {
+        Box::new(into_any_fn_once(self.children))
+    }
+}
+
+impl<F, C> ToChildren<F> for TypedChildren<C>
+where
+    F: FnOnce() -> C + Send + 'static,
+    C: IntoView,
+    C::AsyncOutput: Send,
+{
+    #[inline]
+    #[track_caller]
+    fn to_children(f: F) -> Self {
+        let location = Location::caller();
+        TypedChildren {
+            children: Box::new(move || {
+                let _guard = PanicLocationGuard(location);
+                f().into_view()
+            }),
+            meta: ChildrenMeta {
+                location: Some(location),
+                count: None,
+            },
+            error: None,
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn to_children_with_count(f: F, count: usize) -> Self {
+        let mut children = Self::to_children(f);
+        children.meta.count = Some(count);
+        children
+    }
+}
+
+impl<F, C> From<F> for TypedChildren<C>
+where
+    F: FnOnce() -> C + Send + 'static,
+    C: IntoView,
+    C::AsyncOutput: Send,
+{
+    #[inline]
+    #[track_caller]
+    fn from(f: F) -> Self {
+        Self::to_children(f)
+    }
+}
+
+/// Logs the location at which some children were declared, if rendering them panics.
+struct PanicLocationGuard(&'static Location<'static>);
+
+impl Drop for PanicLocationGuard {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            crate::logging::error!(
+                "panicked while rendering children declared at {}",
+                self.0
+            );
+        }
+    }
+}
+
+/// A typed equivalent to [`ChildrenFnMut`], which takes a generic but preserves type information to
+/// allow the compiler to optimize the view more effectively.
+pub struct TypedChildrenMut<T>(Box<dyn FnMut() -> View<T> + Send>);
+
+impl<T> Debug for TypedChildrenMut<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedChildrenMut")
+            .field(&std::any::type_name::<T>())
+            .finish()
+    }
+}
+
+impl<T> TypedChildrenMut<T> {
+    /// Extracts the inner `children` function.
+    pub fn into_inner(self) -> impl FnMut() -> View<T> + Send {
+        self.0
+    }
+}
+
+impl<F, C> ToChildren<F> for TypedChildrenMut<C>
+where
+    F: FnMut() -> C + Send + 'static,
+    C: IntoView,
+    C::AsyncOutput: Send,
+{
+    #[inline]
+    fn to_children(mut f: F) -> Self {
+        TypedChildrenMut(Box::new(move || f().into_view()))
+    }
+}
+
+/// A typed equivalent to [`ChildrenFn`], which takes a generic but preserves type information to
+/// allow the compiler to optimize the view more effectively.
+pub struct TypedChildrenFn<T>(Arc<dyn Fn() -> View<T> + Send + Sync>);
+
+impl<T> Clone for TypedChildrenFn<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Debug for TypedChildrenFn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedChildrenFn")
+            .field(&std::any::type_name::<T>())
+            .finish()
+    }
+}
+
+impl<T> TypedChildrenFn<T> {
+    /// Extracts the inner `children` function.
+    pub fn into_inner(self) -> Arc<dyn Fn() -> View<T> + Send + Sync> {
+        self.0
+    }
+
+    /// Renders the children, returning the typed view.
+    pub fn to_view(&self) -> View<T> {
+        (self.0)()
+    }
+
+    /// Renders the children and erases the type of the resulting view.
+    pub fn to_any(&self) -> AnyView
+    where
+        T: RenderHtml + Send + 'static,
+    {
+        self.to_view().into_any()
+    }
+
+    /// Erases the type of the children, converting them into [`ChildrenFn`].
+    pub fn into_erased(self) -> ChildrenFn
+    where
+        T: RenderHtml + Send + 'static,
+    {
+        let children = self.0;
+        Arc::new(into_any_fn(move || children()))
+    }
+}
+
+impl<F, C> ToChildren<F> for TypedChildrenFn<C>
+where
+    F: Fn() -> C + Send + Sync + 'static,
+    C: IntoView,
+    C::AsyncOutput: Send,
+{
+    #[inline]
+    fn to_children(f: F) -> Self {
+        TypedChildrenFn(Arc::new(move || f().into_view()))
+    }
+}
+
+/// Renders the children to a string of HTML.
+///
+/// This uses the same synchronous rendering path as [`RenderHtml::to_html`], and is mostly
+/// useful for asserting on the markup produced by a component in tests.
+#[cfg(feature = "ssr")]
+pub fn render_children_to_string(children: Children) -> String {
+    children().to_html()
+}
+
+/// Renders the view produced by a [`ViewFn`] to a string of HTML.
+///
+/// See [`render_children_to_string`].
+#[cfg(feature = "ssr")]
+pub fn render_view_fn_to_string(view_fn: &ViewFn) -> String {
+    view_fn.run().to_html()
+}
+
+/// Renders some [`TypedChildren`] to a string of HTML, without erasing their type.
+///
+/// This goes through [`RenderHtml::to_html`], the same path used when the children are rendered
+/// inline, and any resources or suspense boundaries inside them take their IDs from the current
+/// hydration context as usual. Hydration itself walks the DOM rather than matching IDs, so there
+/// are no other hydration IDs to return. The markup is identical to inline rendering when the
+/// children are the first child of their parent; after a text node, inline rendering may add a
+/// `<!>` marker between adjacent text nodes.
+#[cfg(feature = "ssr")]
+pub fn render_typed_children_to_string<T>(
+    children: TypedChildren<T>,
+) -> String
+where
+    T: RenderHtml,
+{
+    children.into_view().to_html()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::children::{
+        ChildrenFn, ChildrenFnMut, ChildrenFnMutExt, ChildrenKeyed, ToChildren,
+        ViewFn, ViewFnLocal,
+    };
+    use reactive_graph::owner::Owner;
+    use std::sync::OnceLock;
+    use tachys::view::any_view::IntoAny;
+
+    #[test]
+    fn with_limit_allows_calls_up_to_limit() {
+        let children: ChildrenFnMut = Box::new(|| ().into_any());
+        let mut children = children.with_limit(2);
+        children();
+        children();
+    }
+
+    #[test]
+    #[should_panic(expected = "called more than 2 time(s)")]
+    fn with_limit_panics_past_limit() {
+        let children: ChildrenFnMut = Box::new(|| ().into_any());
+        let mut children = children.with_limit(2);
+        children();
+        children();
+        children();
+    }
+
+    #[test]
+    fn children_keyed_keeps_order() {
+        let children =
+            ChildrenKeyed::to_children([(3, "c"), (1, "a"), (2, "b")]);
+        assert_eq!(children.len(), 3);
+        assert_eq!(children.keys().copied().collect::<Vec<_>>(), [3, 1, 2]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "duplicate key in `ChildrenKeyed`")]
+    fn children_keyed_panics_on_duplicate_keys() {
+        ChildrenKeyed::to_children([(1, "a"), (2, "b"), (1, "c")]);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn children_keyed_keeps_last_duplicate() {
+        let children =
+            ChildrenKeyed::to_children([(1, "a"), (2, "b"), (1, "c")]);
+        assert_eq!(children.keys().copied().collect::<Vec<_>>(), [2, 1]);
+    }
+
+    #[test]
+    fn wrappers_keep_view_fn_flags() {
+        assert!(ViewFn::default().timed("default").is_default);
+        assert!(ViewFn::noop().timed("noop").is_noop());
+        assert!(ViewFn::default().with_owner(Owner::new()).is_default);
+        assert!(ViewFn::noop().catch_unwind(ViewFn::default()).is_noop());
+        assert!(ViewFn::default().filter(|| true).is_default);
+        assert!(ViewFn::noop().chain(ViewFn::noop()).is_noop());
+        assert!(!ViewFn::default().chain(ViewFn::from(|| "a")).is_default);
+    }
+
+    #[test]
+    fn view_fn_local_noop_is_not_default() {
+        assert!(ViewFnLocal::noop().is_noop());
+        assert!(!ViewFnLocal::noop().is_default);
+        assert!(!ViewFnLocal::default().is_noop());
+    }
+
+    #[test]
+    fn catch_unwind_restores_owner() {
+        let outer = Owner::new();
+        outer.set();
+        let view = ViewFn::from(|| -> &'static str { panic!("boom") })
+            .with_owner(Owner::new())
+            .catch_unwind(ViewFn::default());
+        _ = view.run();
+        assert_eq!(Owner::current(), Some(outer));
+    }
+
+    #[test]
+    fn into_shared_waits_for_calls_from_other_threads() {
+        let children: ChildrenFnMut = Box::new(|| {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            ().into_any()
+        });
+        let children = children.into_shared();
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| children());
+            }
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "called re-entrantly")]
+    fn into_shared_panics_when_called_re_entrantly() {
+        static CHILDREN: OnceLock<ChildrenFn> = OnceLock::new();
+        let children: ChildrenFnMut =
+            Box::new(|| CHILDREN.get().expect("children are set")());
+        _ = CHILDREN.set(children.into_shared());
+        CHILDREN.get().unwrap()();
     }
 }
@@ -1,5 +1,6 @@
 use super::{
-    fragment_to_tokens, utils::is_nostrip_optional_and_update_key, TagType,
+    fragment_to_tokens_with_count, utils::is_nostrip_optional_and_update_key,
+    TagType,
 };
 use crate::view::{attribute_absolute, utils::filter_prefixed_attrs};
 use proc_macro2::{Ident, TokenStream, TokenTree};
@@ -189,7 +190,7 @@ pub(crate) fn component_to_tokens(
     let children = if node.children.is_empty() {
         quote! {}
     } else {
-        let children = fragment_to_tokens(
+        let children = fragment_to_tokens_with_count(
             &mut node.children,
             TagType::Unknown,
             Some(&mut slots),
@@ -212,7 +213,7 @@ pub(crate) fn component_to_tokens(
         }
         */
 
-        if let Some(children) = children {
+        if let Some((children, count)) = children {
             let bindables =
                 items_to_bind.iter().map(|ident| quote! { #ident, });
 
@@ -234,7 +235,7 @@ pub(crate) fn component_to_tokens(
                     .children({
                         #(#clonables)*
 
-                        ::leptos::children::ToChildren::to_children(move || #children)
+                        ::leptos::children::ToChildren::to_children_with_count(move || #children, #count)
                     })
                 }
             }
@@ -455,6 +455,27 @@ fn fragment_to_tokens(
     view_marker: Option<&str>,
     disable_inert_html: bool,
 ) -> Option<TokenStream> {
+    fragment_to_tokens_with_count(
+        nodes,
+        parent_type,
+        parent_slots,
+        global_class,
+        view_marker,
+        disable_inert_html,
+    )
+    .map(|(tokens, _)| tokens)
+}
+
+/// Like [`fragment_to_tokens`], but also returns the number of top-level children in the
+/// fragment, not counting slots.
+fn fragment_to_tokens_with_count(
+    nodes: &mut [Node<impl CustomNode>],
+    parent_type: TagType,
+    parent_slots: Option<&mut HashMap<String, Vec<TokenStream>>>,
+    global_class: Option<&TokenTree>,
+    view_marker: Option<&str>,
+    disable_inert_html: bool,
+) -> Option<(TokenStream, usize)> {
     let children = children_to_tokens(
         nodes,
         parent_type,
@@ -464,7 +485,8 @@ fn fragment_to_tokens(
         true,
         disable_inert_html,
     );
-    if children.is_empty() {
+    let count = children.len();
+    let tokens = if children.is_empty() {
         None
     } else if children.len() == 1 {
         children.into_iter().next()
@@ -472,20 +494,23 @@ fn fragment_to_tokens(
         // implementations of various traits used in routing and rendering are implemented for
         // tuples of sizes 0, 1, 2, 3, ... N. N varies but is > 16. The traits are also implemented
         // for tuples of tuples, so if we have more than 16 items, we can split them out into
-        // multiple tuples.
+        // multiple tuples. `Chunked` renders like the nested tuples, but flattens them when they
+        // are converted into a `Fragment`, and the trailing comma keeps a lone child in a 1-tuple
+        // so that every chunk can be flattened.
         let chunks = children.chunks(16).map(|children| {
             quote! {
-                (#(#children),*)
+                (#(#children,)*)
             }
         });
         Some(quote! {
-             (#(#chunks),*)
+            ::leptos::tachys::view::fragment::Chunked((#(#chunks),*))
         })
     } else {
         Some(quote! {
             (#(#children),*)
         })
-    }
+    };
+    tokens.map(|tokens| (tokens, count))
 }
 
 fn children_to_tokens(
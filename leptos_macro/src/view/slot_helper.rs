@@ -1,5 +1,7 @@
 use super::{convert_to_snake_case, ident_from_tag_name};
-use crate::view::{fragment_to_tokens, utils::filter_prefixed_attrs, TagType};
+use crate::view::{
+    fragment_to_tokens_with_count, utils::filter_prefixed_attrs, TagType,
+};
 use proc_macro2::{Ident, TokenStream, TokenTree};
 use quote::{quote, quote_spanned};
 use rstml::node::{CustomNode, KeyedAttribute, NodeAttribute, NodeElement};
@@ -97,7 +99,7 @@ pub(crate) fn slot_to_tokens(
     let children = if node.children.is_empty() {
         quote! {}
     } else {
-        let children = fragment_to_tokens(
+        let children = fragment_to_tokens_with_count(
             &mut node.children,
             TagType::Unknown,
             Some(&mut slots),
@@ -121,7 +123,7 @@ pub(crate) fn slot_to_tokens(
         */
         let view_marker = quote! {};
 
-        if let Some(children) = children {
+        if let Some((children, count)) = children {
             let bindables =
                 items_to_bind.iter().map(|ident| quote! { #ident, });
 
@@ -144,7 +146,7 @@ pub(crate) fn slot_to_tokens(
                     .children({
                         #(#clonables)*
 
-                        ::leptos::children::ToChildren::to_children(move || #children #view_marker)
+                        ::leptos::children::ToChildren::to_children_with_count(move || #children #view_marker, #count)
                     })
                 }
             }
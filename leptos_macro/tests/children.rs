@@ -0,0 +1,103 @@
+use leptos::{children::ToChildren, prelude::*};
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+struct MyChildren(Box<dyn FnOnce() -> AnyView + Send>);
+
+impl<F, C> ToChildren<F> for MyChildren
+where
+    F: FnOnce() -> C + Send + 'static,
+    C: IntoView + 'static,
+{
+    fn to_children(f: F) -> Self {
+        MyChildren(Box::new(move || f().into_any()))
+    }
+}
+
+#[component]
+fn Wrapper(rendered: Arc<AtomicBool>, children: MyChildren) -> impl IntoView {
+    rendered.store(true, Ordering::Relaxed);
+    (children.0)()
+}
+
+#[test]
+fn custom_children_type() {
+    let rendered = Arc::new(AtomicBool::new(false));
+    let _ = view! {
+        <Wrapper rendered=Arc::clone(&rendered)>
+            <p>"Hello"</p>
+        </Wrapper>
+    };
+    assert!(rendered.load(Ordering::Relaxed));
+}
+
+#[component]
+fn OptionalWrapper(
+    rendered: Arc<AtomicBool>,
+    #[prop(optional)] children: Option<MyChildren>,
+) -> impl IntoView {
+    rendered.store(children.is_some(), Ordering::Relaxed);
+    children.map(|children| (children.0)())
+}
+
+#[test]
+fn optional_custom_children_type() {
+    let rendered = Arc::new(AtomicBool::new(false));
+    let _ = view! {
+        <OptionalWrapper rendered=Arc::clone(&rendered)>
+            <p>"Hello"</p>
+        </OptionalWrapper>
+    };
+    assert!(rendered.load(Ordering::Relaxed));
+
+    let rendered = Arc::new(AtomicBool::new(true));
+    let _ = view! { <OptionalWrapper rendered=Arc::clone(&rendered)/> };
+    assert!(!rendered.load(Ordering::Relaxed));
+}
+
+#[component]
+fn Counted<T>(
+    count: Arc<AtomicUsize>,
+    children: TypedChildren<T>,
+) -> impl IntoView
+where
+    T: IntoView + 'static,
+{
+    count.store(children.count().unwrap_or_default(), Ordering::Relaxed);
+}
+
+#[test]
+fn typed_children_count() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let _ = view! {
+        <Counted count=Arc::clone(&count)>
+            <p>"Hello"</p>
+            <p>"World"</p>
+            "!"
+        </Counted>
+    };
+    assert_eq!(count.load(Ordering::Relaxed), 3);
+}
+
+#[component]
+fn FragmentLen(
+    len: Arc<AtomicUsize>,
+    children: ChildrenFragment,
+) -> impl IntoView {
+    len.store(children().len(), Ordering::Relaxed);
+}
+
+#[test]
+fn fragment_children_are_flat() {
+    let len = Arc::new(AtomicUsize::new(0));
+    let _ = view! {
+        <FragmentLen len=Arc::clone(&len)>
+            <p>"1"</p> <p>"2"</p> <p>"3"</p> <p>"4"</p> <p>"5"</p> <p>"6"</p>
+            <p>"7"</p> <p>"8"</p> <p>"9"</p> <p>"10"</p> <p>"11"</p> <p>"12"</p>
+            <p>"13"</p> <p>"14"</p> <p>"15"</p> <p>"16"</p> <p>"17"</p>
+        </FragmentLen>
+    };
+    assert_eq!(len.load(Ordering::Relaxed), 17);
+}
@@ -213,11 +213,21 @@ impl Owner {
         };
         #[cfg(feature = "sandboxed-arenas")]
         Arena::set(&self.inner.read().or_poisoned().arena);
-        let val = fun();
-        OWNER.with(|o| {
-            *o.borrow_mut() = prev;
-        });
-        val
+
+        // restores the previous owner even if `fun` panics
+        struct RestoreOwner(Option<Owner>);
+
+        impl Drop for RestoreOwner {
+            fn drop(&mut self) {
+                let prev = self.0.take();
+                OWNER.with(|o| {
+                    *o.borrow_mut() = prev;
+                });
+            }
+        }
+
+        let _restore = RestoreOwner(prev);
+        fun()
     }
 
     /// Cleans up this owner, the given function with this as the current `Owner`.
@@ -3,6 +3,7 @@ use crate::{ChooseView, GeneratedRouteData, MatchParams};
 use core::iter;
 use either_of::*;
 use std::borrow::Cow;
+use tachys::view::fragment::Chunked;
 
 impl MatchParams for () {
     fn to_params(&self) -> Vec<(Cow<'static, str>, String)> {
@@ -96,6 +97,28 @@ where
     }
 }
 
+// the `view!` macro wraps more than 16 routes in `Chunked`, which matches like the inner tuples
+impl<T> MatchNestedRoutes for Chunked<T>
+where
+    T: MatchNestedRoutes,
+{
+    type Data = T::Data;
+    type Match = T::Match;
+
+    fn match_nested<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> (Option<(RouteMatchId, Self::Match)>, &'a str) {
+        self.0.match_nested(path)
+    }
+
+    fn generate_routes(
+        &self,
+    ) -> impl IntoIterator<Item = GeneratedRouteData> + '_ {
+        self.0.generate_routes()
+    }
+}
+
 impl<A, B> MatchParams for Either<A, B>
 where
     A: MatchParams,
@@ -1,6 +1,16 @@
-use super::any_view::{AnyView, IntoAny};
+use super::{
+    add_attr::AddAnyAttr,
+    any_view::{AnyView, IntoAny},
+    Position, PositionState, Render, RenderHtml, ToTemplate,
+};
+use crate::{html::attribute::Attribute, hydration::Cursor, ssr::StreamBuilder};
+use std::collections::VecDeque;
 
 /// A typed-erased collection of different views.
+///
+/// Children passed with the `view!` macro become one view each, however many there are, so the
+/// index-based helpers like [`Fragment::split_at`] and [`Fragment::partition`] count declared
+/// children. A child that is itself a tuple, list or fragment is still a single view.
 pub struct Fragment {
     /// The nodes contained in the fragment.
     pub nodes: Vec<AnyView>,
@@ -18,6 +28,12 @@ impl FromIterator<AnyView> for Fragment {
     }
 }
 
+impl Extend<AnyView> for Fragment {
+    fn extend<T: IntoIterator<Item = AnyView>>(&mut self, iter: T) {
+        self.nodes.extend(iter);
+    }
+}
+
 impl From<AnyView> for Fragment {
     fn from(view: AnyView) -> Self {
         Fragment::new(vec![view])
@@ -36,6 +52,229 @@ impl Fragment {
     pub fn new(nodes: Vec<AnyView>) -> Self {
         Self { nodes }
     }
+
+    /// Creates a [`Fragment`] by calling each of the given functions, in iteration order.
+    ///
+    /// The functions are called immediately. To defer rendering until the fragment is needed, call
+    /// this from inside the children function instead, e.g.
+    /// `ToChildren::to_children(move || Fragment::from_fns(fns))`.
+    pub fn from_fns<I, F, V>(fns: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+        F: FnOnce() -> V,
+        V: IntoAny,
+    {
+        fns.into_iter().map(|f| f().into_any()).collect()
+    }
+
+    /// Returns the number of views in the fragment.
+    ///
+    /// This only counts the top-level views: a nested tuple or list counts as a single view.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the fragment contains no views.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns an iterator over the views in the fragment.
+    #[inline(always)]
+    pub fn iter(&self) -> std::slice::Iter<'_, AnyView> {
+        self.nodes.iter()
+    }
+
+    /// Extracts the views in the fragment, in order.
+    ///
+    /// Each view can then be built and mounted on its own, for components that manage their own
+    /// mount points. Each one must be built with [`Render::build`](crate::view::Render::build),
+    /// which returns its state, and that state must be kept alive for as long as the view should
+    /// stay mounted:
+    ///
+    /// ```ignore
+    /// let states = fragment
+    ///     .into_views()
+    ///     .into_iter()
+    ///     .zip(&parents)
+    ///     .map(|(view, parent)| {
+    ///         let mut state = view.build();
+    ///         state.mount(parent, None);
+    ///         state
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// ```
+    #[inline(always)]
+    pub fn into_views(self) -> Vec<AnyView> {
+        self.nodes
+    }
+
+    /// Returns the first view in the fragment, or `None` if it is empty.
+    ///
+    /// For a fragment with a single view, this is the same view as [`Fragment::last`].
+    #[inline(always)]
+    pub fn first(&self) -> Option<&AnyView> {
+        self.nodes.first()
+    }
+
+    /// Returns the last view in the fragment, or `None` if it is empty.
+    ///
+    /// For a fragment with a single view, this is the same view as [`Fragment::first`].
+    #[inline(always)]
+    pub fn last(&self) -> Option<&AnyView> {
+        self.nodes.last()
+    }
+
+    /// Removes the first view in the fragment, returning it along with the remaining views.
+    ///
+    /// The last view can then be taken with [`VecDeque::pop_back`]. For a fragment with a single
+    /// view, that view is returned as the first one and the remaining views are empty.
+    pub fn into_first_last(self) -> (Option<AnyView>, VecDeque<AnyView>) {
+        let mut nodes = VecDeque::from(self.nodes);
+        (nodes.pop_front(), nodes)
+    }
+
+    /// Transforms each view in the fragment, keeping them in the same order.
+    pub fn map<F, V>(self, f: F) -> Fragment
+    where
+        F: FnMut(AnyView) -> V,
+        V: IntoAny,
+    {
+        self.nodes.into_iter().map(f).map(IntoAny::into_any).collect()
+    }
+
+    /// Splits the fragment into the first `n` views and the rest.
+    ///
+    /// If `n` is greater than the number of views, the first fragment contains all of them and the
+    /// second is empty. The views are moved, not rebuilt.
+    pub fn split_at(mut self, n: usize) -> (Fragment, Fragment) {
+        let rest = self.nodes.split_off(n.min(self.nodes.len()));
+        (self, Fragment::new(rest))
+    }
+
+    /// Inserts a new separator view between each pair of views in the fragment.
+    ///
+    /// `separator` is called once for each separator, so every separator is a distinct view. No
+    /// separator is added before the first view or after the last one, so a fragment with zero or
+    /// one views is returned unchanged.
+    pub fn intersperse<F, V>(self, mut separator: F) -> Fragment
+    where
+        F: FnMut() -> V,
+        V: IntoAny,
+    {
+        if self.nodes.len() < 2 {
+            return self;
+        }
+        let mut nodes = Vec::with_capacity(self.nodes.len() * 2 - 1);
+        for (idx, view) in self.nodes.into_iter().enumerate() {
+            if idx > 0 {
+                nodes.push(separator().into_any());
+            }
+            nodes.push(view);
+        }
+        Fragment::new(nodes)
+    }
+
+    /// Splits the fragment into the views for which `pred` returns `true` and those for which it
+    /// returns `false`.
+    ///
+    /// `pred` is given the original index of each view. Views keep their relative order within
+    /// each of the two fragments.
+    pub fn partition<P>(self, mut pred: P) -> (Fragment, Fragment)
+    where
+        P: FnMut(usize) -> bool,
+    {
+        let (matched, unmatched): (Vec<_>, Vec<_>) = self
+            .nodes
+            .into_iter()
+            .enumerate()
+            .partition(|(idx, _)| pred(*idx));
+        (
+            matched.into_iter().map(|(_, view)| view).collect(),
+            unmatched.into_iter().map(|(_, view)| view).collect(),
+        )
+    }
+
+    /// Splits the fragment into groups of `size` views, in order.
+    ///
+    /// The last group contains fewer than `size` views if the number of views is not a multiple of
+    /// `size`.
+    ///
+    /// ## Panics
+    /// Panics if `size` is 0.
+    pub fn chunks(self, size: usize) -> Vec<Fragment> {
+        assert!(size != 0, "`Fragment::chunks` size must be greater than 0");
+        let mut chunks = Vec::with_capacity(self.nodes.len().div_ceil(size));
+        let mut nodes = self.nodes.into_iter().peekable();
+        while nodes.peek().is_some() {
+            chunks.push(nodes.by_ref().take(size).collect());
+        }
+        chunks
+    }
+
+    /// Removes consecutive views that have the same key, keeping the first one of each run.
+    ///
+    /// Views cannot be compared themselves, so `f` is given the original index of each view to
+    /// compute its key. As with [`Vec::dedup_by_key`], only adjacent duplicates are removed.
+    pub fn dedup_by_key<K, F>(self, mut f: F) -> Fragment
+    where
+        K: PartialEq,
+        F: FnMut(usize) -> K,
+    {
+        let mut keyed = self
+            .nodes
+            .into_iter()
+            .enumerate()
+            .map(|(idx, view)| (f(idx), view))
+            .collect::<Vec<_>>();
+        keyed.dedup_by(|(a, _), (b, _)| a == b);
+        keyed.into_iter().map(|(_, view)| view).collect()
+    }
+
+    /// Reverses the order of the views in the fragment.
+    pub fn reversed(mut self) -> Fragment {
+        self.nodes.reverse();
+        self
+    }
+
+    /// Sorts the views in the fragment by a key computed from each view's original index.
+    ///
+    /// Views cannot be compared themselves, so `f` is given the position of each view before
+    /// sorting. The sort is stable: views with equal keys keep their relative order.
+    pub fn sorted_by_key<K, F>(self, mut f: F) -> Fragment
+    where
+        K: Ord,
+        F: FnMut(usize) -> K,
+    {
+        let mut keyed = self
+            .nodes
+            .into_iter()
+            .enumerate()
+            .map(|(idx, view)| (f(idx), view))
+            .collect::<Vec<_>>();
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+        keyed.into_iter().map(|(_, view)| view).collect()
+    }
+}
+
+impl IntoIterator for Fragment {
+    type Item = AnyView;
+    type IntoIter = std::vec::IntoIter<AnyView>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Fragment {
+    type Item = &'a AnyView;
+    type IntoIter = std::slice::Iter<'a, AnyView>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.iter()
+    }
 }
 
 impl<T> IntoFragment for Vec<T>
@@ -56,6 +295,144 @@ where
     }
 }
 
+/// A tuple of views that the `view!` macro has split into nested tuples of at most 16 views.
+///
+/// This renders exactly like the nested tuple it wraps, but [`IntoFragment`] flattens it, so the
+/// fragment has one view per child rather than one per group of 16.
+#[derive(Debug, Clone)]
+pub struct Chunked<T>(pub T);
+
+impl<T: Render> Render for Chunked<T> {
+    type State = T::State;
+
+    fn build(self) -> Self::State {
+        self.0.build()
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.0.rebuild(state)
+    }
+}
+
+impl<T: RenderHtml> RenderHtml for Chunked<T> {
+    type AsyncOutput = Chunked<T::AsyncOutput>;
+
+    const MIN_LENGTH: usize = T::MIN_LENGTH;
+    const EXISTS: bool = T::EXISTS;
+
+    fn html_len(&self) -> usize {
+        self.0.html_len()
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+    ) {
+        self.0
+            .to_html_with_buf(buf, position, escape, mark_branches);
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+    ) where
+        Self: Sized,
+    {
+        self.0.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+        );
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.0.hydrate::<FROM_SERVER>(cursor, position)
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        Chunked(self.0.resolve().await)
+    }
+
+    fn dry_resolve(&mut self) {
+        self.0.dry_resolve();
+    }
+}
+
+impl<T: ToTemplate> ToTemplate for Chunked<T> {
+    const TEMPLATE: &'static str = T::TEMPLATE;
+    const CLASS: &'static str = T::CLASS;
+    const STYLE: &'static str = T::STYLE;
+
+    fn to_template(
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+        position: &mut Position,
+    ) {
+        T::to_template(buf, class, style, inner_html, position)
+    }
+}
+
+impl<T: AddAnyAttr> AddAnyAttr for Chunked<T> {
+    type Output<SomeNewAttr: Attribute> = Chunked<T::Output<SomeNewAttr>>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        Chunked(self.0.add_any_attr(attr))
+    }
+}
+
+macro_rules! chunked_tuples {
+	($($ty:ident),*) => {
+		impl<$($ty),*> IntoFragment for Chunked<($($ty,)*)>
+		where
+			$($ty: IntoFragment),*,
+
+		{
+            fn into_fragment(self) -> Fragment {
+                #[allow(non_snake_case)]
+			    let ($($ty,)*) = self.0;
+                let mut nodes = Vec::new();
+                $(nodes.extend($ty.into_fragment());)*
+                Fragment::new(nodes)
+            }
+        }
+    }
+}
+
+chunked_tuples!(A, B);
+chunked_tuples!(A, B, C);
+chunked_tuples!(A, B, C, D);
+chunked_tuples!(A, B, C, D, E);
+chunked_tuples!(A, B, C, D, E, F);
+chunked_tuples!(A, B, C, D, E, F, G);
+chunked_tuples!(A, B, C, D, E, F, G, H);
+chunked_tuples!(A, B, C, D, E, F, G, H, I);
+chunked_tuples!(A, B, C, D, E, F, G, H, I, J);
+chunked_tuples!(A, B, C, D, E, F, G, H, I, J, K);
+chunked_tuples!(A, B, C, D, E, F, G, H, I, J, K, L);
+chunked_tuples!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+chunked_tuples!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+chunked_tuples!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+chunked_tuples!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
 macro_rules! tuples {
 	($($ty:ident),*) => {
 		impl<$($ty),*> IntoFragment for ($($ty,)*)
@@ -103,3 +480,87 @@ tuples!(
     A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
     Z
 );
+
+#[cfg(test)]
+mod tests {
+    use super::{Chunked, Fragment, IntoFragment};
+    #[cfg(feature = "ssr")]
+    use crate::{
+        html::element::{p, ElementChild},
+        view::{any_view::IntoAny, RenderHtml},
+    };
+
+    #[cfg(feature = "ssr")]
+    fn fragment(views: &[&'static str]) -> Fragment {
+        views.iter().map(|view| view.into_any()).collect()
+    }
+
+    #[cfg(feature = "ssr")]
+    fn html(fragment: Fragment) -> Vec<String> {
+        fragment.into_iter().map(|view| view.to_html()).collect()
+    }
+
+    #[test]
+    fn len_counts_top_level_views() {
+        let empty = Fragment::new(Vec::new());
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+
+        assert_eq!(("a", "b", "c").into_fragment().len(), 3);
+        assert_eq!((("a", "b"), "c").into_fragment().len(), 2);
+        assert_eq!(vec!["a", "b"].into_fragment().len(), 2);
+        assert_eq!(Chunked((("a", "b"), ("c",))).into_fragment().len(), 3);
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn helpers_keep_views_in_order() {
+        let keys = [1, 1, 2, 1];
+        let deduped =
+            fragment(&["a", "b", "c", "d"]).dedup_by_key(|idx| keys[idx]);
+        assert_eq!(html(deduped), ["a", "c", "d"]);
+
+        let keys = [2, 1, 2, 1];
+        let sorted =
+            fragment(&["a", "b", "c", "d"]).sorted_by_key(|idx| keys[idx]);
+        assert_eq!(html(sorted), ["b", "d", "a", "c"]);
+
+        let (even, odd) =
+            fragment(&["a", "b", "c", "d", "e"]).partition(|idx| idx % 2 == 0);
+        assert_eq!(html(even), ["a", "c", "e"]);
+        assert_eq!(html(odd), ["b", "d"]);
+
+        let chunks = fragment(&["a", "b", "c", "d", "e"]).chunks(2);
+        let chunks = chunks.into_iter().map(html).collect::<Vec<_>>();
+        assert_eq!(chunks, [vec!["a", "b"], vec!["c", "d"], vec!["e"]]);
+        assert!(fragment(&[]).chunks(2).is_empty());
+
+        let (first, rest) = fragment(&["a", "b"]).split_at(5);
+        assert_eq!(html(first), ["a", "b"]);
+        assert!(rest.is_empty());
+
+        let interspersed = fragment(&["a", "b", "c"]).intersperse(|| "-");
+        assert_eq!(html(interspersed), ["a", "-", "b", "-", "c"]);
+        assert!(fragment(&[]).intersperse(|| "-").is_empty());
+        assert_eq!(html(fragment(&["a"]).intersperse(|| "-")), ["a"]);
+
+        let mapped = fragment(&["a", "b"]).map(|view| p().child(view));
+        assert_eq!(html(mapped), ["<p>a</p>", "<p>b</p>"]);
+
+        let single = fragment(&["a"]);
+        assert!(std::ptr::eq(single.first().unwrap(), single.last().unwrap()));
+        let (first, rest) = single.into_first_last();
+        assert_eq!(first.unwrap().to_html(), "a");
+        assert!(rest.is_empty());
+
+        let mut fns = Fragment::from_fns(["a", "b"].map(|view| move || view));
+        fns.extend(fragment(&["c"]));
+        assert_eq!(html(fns), ["a", "b", "c"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "size must be greater than 0")]
+    fn chunks_panics_on_zero_size() {
+        Fragment::new(Vec::new()).chunks(0);
+    }
+}